@@ -0,0 +1,62 @@
+use crate::mirror::Mirror;
+use crate::progress::{ProgressEvent, ProgressFormat};
+use std::fmt::Display;
+use std::str::FromStr;
+use std::sync::{mpsc, Arc};
+use thiserror::Error;
+
+#[derive(Error, Debug)]
+pub enum AppError {
+    #[error("request failed: {0}")]
+    Reqwest(#[from] reqwest::Error),
+    #[error("{0}")]
+    Message(String),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Protocol {
+    Http,
+    Https,
+    Rsync,
+    Ftp,
+}
+
+impl FromStr for Protocol {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "http" => Ok(Protocol::Http),
+            "https" => Ok(Protocol::Https),
+            "rsync" => Ok(Protocol::Rsync),
+            "ftp" => Ok(Protocol::Ftp),
+            _ => Err(()),
+        }
+    }
+}
+
+pub struct Config {
+    pub allowed_protocols: Vec<Protocol>,
+    /// Controls how `ProgressEvent`s are rendered at the sink, set via
+    /// `--progress-format={text,jsonl}`.
+    pub progress_format: ProgressFormat,
+}
+
+impl Config {
+    pub fn is_protocol_allowed(&self, protocol: &Protocol) -> bool {
+        self.allowed_protocols.contains(protocol)
+    }
+}
+
+pub trait FetchMirrors {
+    fn fetch_mirrors(
+        &self,
+        config: Arc<Config>,
+        tx_progress: mpsc::Sender<ProgressEvent>,
+    ) -> Result<Vec<Mirror>, AppError>;
+}
+
+pub trait LogFormatter {
+    fn format_comment(&self, message: impl Display) -> String;
+    fn format_mirror(&self, mirror: &Mirror) -> String;
+}
@@ -0,0 +1,15 @@
+use std::convert::Infallible;
+use std::str::FromStr;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Country {
+    Other,
+}
+
+impl FromStr for Country {
+    type Err = Infallible;
+
+    fn from_str(_s: &str) -> Result<Self, Self::Err> {
+        Ok(Country::Other)
+    }
+}
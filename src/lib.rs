@@ -0,0 +1,7 @@
+pub mod config;
+pub mod countries;
+pub mod mirror;
+pub mod mirror_status;
+pub mod progress;
+pub mod target_configs;
+pub mod targets;
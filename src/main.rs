@@ -0,0 +1,60 @@
+use rate_mirrors::config::{Config, FetchMirrors, LogFormatter, Protocol};
+use rate_mirrors::progress::{drain_progress_to_stderr, ProgressFormat};
+use rate_mirrors::target_configs::endeavouros::EndeavourOSTarget;
+use std::process::ExitCode;
+use std::str::FromStr;
+use std::sync::{mpsc, Arc};
+use std::thread;
+
+/// Parses `--progress-format={text,jsonl}` out of argv, defaulting to
+/// [`ProgressFormat::Text`] when the flag is absent.
+fn parse_progress_format(args: &[String]) -> Result<ProgressFormat, String> {
+    for arg in args {
+        if let Some(value) = arg.strip_prefix("--progress-format=") {
+            return ProgressFormat::from_str(value);
+        }
+    }
+    Ok(ProgressFormat::default())
+}
+
+fn main() -> ExitCode {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+
+    let progress_format = match parse_progress_format(&args) {
+        Ok(format) => format,
+        Err(err) => {
+            eprintln!("{}", err);
+            return ExitCode::FAILURE;
+        }
+    };
+
+    let config = Arc::new(Config {
+        allowed_protocols: vec![Protocol::Http, Protocol::Https, Protocol::Rsync],
+        progress_format,
+    });
+
+    let target = EndeavourOSTarget::default();
+    let (tx_progress, rx_progress) = mpsc::channel();
+
+    let fetch_config = Arc::clone(&config);
+    let fetch_handle = thread::spawn(move || target.fetch_mirrors(fetch_config, tx_progress));
+
+    // Drain on the main thread as progress events arrive, rather than after
+    // fetch_mirrors returns, so `--progress-format=jsonl` consumers can pipe
+    // events into other tools in real time.
+    drain_progress_to_stderr(rx_progress, config.progress_format);
+
+    match fetch_handle.join().expect("fetch_mirrors thread panicked") {
+        Ok(mirrors) => {
+            let target = EndeavourOSTarget::default();
+            for mirror in &mirrors {
+                println!("{}", target.format_mirror(mirror));
+            }
+            ExitCode::SUCCESS
+        }
+        Err(err) => {
+            eprintln!("{}", err);
+            ExitCode::FAILURE
+        }
+    }
+}
@@ -0,0 +1,9 @@
+use crate::countries::Country;
+use url::Url;
+
+#[derive(Debug, Clone)]
+pub struct Mirror {
+    pub country: Option<Country>,
+    pub url: Url,
+    pub url_to_test: Url,
+}
@@ -0,0 +1,197 @@
+use crate::config::AppError;
+use crate::mirror::Mirror;
+use serde::Deserialize;
+use std::time::Duration;
+
+#[derive(Debug, Deserialize)]
+pub struct MirrorStatus {
+    pub cutoff: u64,
+    pub last_check: String,
+    pub num_checks: u64,
+    pub check_frequency: u64,
+    pub urls: Vec<MirrorStatusEntry>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct MirrorStatusEntry {
+    pub url: String,
+    pub protocol: String,
+    pub last_sync: Option<String>,
+    pub completion_pct: f64,
+    pub delay: Option<i64>,
+    pub duration_avg: Option<f64>,
+    pub duration_stddev: Option<f64>,
+    pub score: Option<f64>,
+}
+
+pub async fn fetch_mirror_status(
+    status_url: &str,
+    timeout: Duration,
+) -> Result<MirrorStatus, AppError> {
+    Ok(reqwest::Client::new()
+        .get(status_url)
+        .timeout(timeout)
+        .send()
+        .await?
+        .json::<MirrorStatus>()
+        .await?)
+}
+
+fn normalized_host_path(url: &str) -> Option<(String, String)> {
+    let parsed = url::Url::parse(url).ok()?;
+    let host = parsed.host_str()?.to_string();
+    let path = parsed.path().trim_end_matches('/').to_string();
+    Some((host, path))
+}
+
+/// Returns `false` if `mirror` is found in `status` but reports as unsynced,
+/// below `min_completion_pct`, or past `max_delay_secs`. Mirrors absent from
+/// `status` (e.g. not covered by the status endpoint) are kept.
+pub fn is_mirror_healthy(
+    mirror: &Mirror,
+    status: &MirrorStatus,
+    min_completion_pct: f64,
+    max_delay_secs: i64,
+) -> bool {
+    let target = match normalized_host_path(mirror.url.as_str()) {
+        Some(target) => target,
+        None => return true,
+    };
+
+    match status
+        .urls
+        .iter()
+        .find(|entry| normalized_host_path(&entry.url).as_ref() == Some(&target))
+    {
+        Some(entry) => {
+            entry.last_sync.is_some()
+                && entry.completion_pct >= min_completion_pct
+                && entry.delay.is_none_or(|delay| delay <= max_delay_secs)
+        }
+        None => true,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use url::Url;
+
+    fn mirror(url: &str) -> Mirror {
+        let url = Url::parse(url).unwrap();
+        Mirror {
+            country: None,
+            url_to_test: url.clone(),
+            url,
+        }
+    }
+
+    fn entry(url: &str, last_sync: Option<&str>, completion_pct: f64, delay: Option<i64>) -> MirrorStatusEntry {
+        MirrorStatusEntry {
+            url: url.to_string(),
+            protocol: "https".to_string(),
+            last_sync: last_sync.map(str::to_string),
+            completion_pct,
+            delay,
+            duration_avg: None,
+            duration_stddev: None,
+            score: None,
+        }
+    }
+
+    fn status(entries: Vec<MirrorStatusEntry>) -> MirrorStatus {
+        MirrorStatus {
+            cutoff: 0,
+            last_check: String::new(),
+            num_checks: 0,
+            check_frequency: 0,
+            urls: entries,
+        }
+    }
+
+    #[test]
+    fn mirror_absent_from_status_is_healthy() {
+        let status = status(vec![]);
+        assert!(is_mirror_healthy(
+            &mirror("https://example.com/repo/"),
+            &status,
+            1.0,
+            86_400
+        ));
+    }
+
+    #[test]
+    fn null_last_sync_is_unhealthy() {
+        let status = status(vec![entry("https://example.com/repo/", None, 1.0, Some(0))]);
+        assert!(!is_mirror_healthy(
+            &mirror("https://example.com/repo/"),
+            &status,
+            1.0,
+            86_400
+        ));
+    }
+
+    #[test]
+    fn below_completion_threshold_is_unhealthy() {
+        let status = status(vec![entry(
+            "https://example.com/repo/",
+            Some("2024-01-01"),
+            0.95,
+            Some(0),
+        )]);
+        assert!(!is_mirror_healthy(
+            &mirror("https://example.com/repo/"),
+            &status,
+            0.99,
+            86_400
+        ));
+    }
+
+    #[test]
+    fn past_max_delay_is_unhealthy() {
+        let status = status(vec![entry(
+            "https://example.com/repo/",
+            Some("2024-01-01"),
+            1.0,
+            Some(100_000),
+        )]);
+        assert!(!is_mirror_healthy(
+            &mirror("https://example.com/repo/"),
+            &status,
+            1.0,
+            86_400
+        ));
+    }
+
+    #[test]
+    fn null_delay_is_healthy() {
+        let status = status(vec![entry(
+            "https://example.com/repo/",
+            Some("2024-01-01"),
+            1.0,
+            None,
+        )]);
+        assert!(is_mirror_healthy(
+            &mirror("https://example.com/repo/"),
+            &status,
+            1.0,
+            86_400
+        ));
+    }
+
+    #[test]
+    fn host_path_normalization_ignores_trailing_slash() {
+        let status = status(vec![entry(
+            "https://example.com/repo",
+            Some("2024-01-01"),
+            1.0,
+            Some(0),
+        )]);
+        assert!(is_mirror_healthy(
+            &mirror("https://example.com/repo/"),
+            &status,
+            1.0,
+            86_400
+        ));
+    }
+}
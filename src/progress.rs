@@ -0,0 +1,70 @@
+use serde::Serialize;
+use std::str::FromStr;
+use std::sync::mpsc::Receiver;
+
+/// Machine-readable progress reported while fetching and ranking mirrors.
+/// Human-readable text is derived from these at the sink via [`ProgressEvent::to_text`];
+/// only the free-form `Stage` message and `MirrorFailed` reason carry text
+/// that targets write themselves.
+#[derive(Debug, Clone, Serialize)]
+#[serde(tag = "event", rename_all = "snake_case")]
+pub enum ProgressEvent {
+    MirrorVersioned { url: String, version: usize },
+    MirrorFailed { url: String, reason: String },
+    EmptyState { url: String },
+    Stage(String),
+}
+
+impl ProgressEvent {
+    pub fn to_text(&self) -> String {
+        match self {
+            ProgressEvent::MirrorVersioned { url, version } => {
+                format!("FETCHED MIRROR VERSION {}: {}", version, url)
+            }
+            ProgressEvent::MirrorFailed { url, reason } => format!("{}: {}", reason, url),
+            ProgressEvent::EmptyState { url } => format!("EMPTY MIRROR STATE: {}", url),
+            ProgressEvent::Stage(message) => message.clone(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ProgressFormat {
+    #[default]
+    Text,
+    Jsonl,
+}
+
+impl FromStr for ProgressFormat {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "text" => Ok(ProgressFormat::Text),
+            "jsonl" => Ok(ProgressFormat::Jsonl),
+            other => Err(format!("unknown progress format: {}", other)),
+        }
+    }
+}
+
+/// Renders a single event for `--progress-format`: a plain line in `text`
+/// mode, or one JSON object per line in `jsonl` mode.
+pub fn format_event(event: &ProgressEvent, format: ProgressFormat) -> String {
+    match format {
+        ProgressFormat::Text => event.to_text(),
+        ProgressFormat::Jsonl => {
+            serde_json::to_string(event).expect("ProgressEvent always serializes")
+        }
+    }
+}
+
+/// Drains a target's progress channel to stderr, one rendered event per
+/// line. This is the sink side of the `tx_progress`/`rx_progress` pair: the
+/// caller runs `FetchMirrors::fetch_mirrors` on another thread and calls this
+/// on the receiving end so `--progress-format=jsonl` consumers can pipe
+/// versioning/filtering progress into other tools in real time.
+pub fn drain_progress_to_stderr(rx_progress: Receiver<ProgressEvent>, format: ProgressFormat) {
+    for event in rx_progress {
+        eprintln!("{}", format_event(&event, format));
+    }
+}
@@ -0,0 +1,70 @@
+#[derive(Debug, Clone)]
+pub struct EndeavourOSTarget {
+    pub mirror_list_file: String,
+    pub path_to_test: String,
+    pub comment_prefix: String,
+    pub version_mirror_timeout: u64,
+    pub version_mirror_concurrency: usize,
+    pub fetch_mirrors_timeout: u64,
+
+    /// URL of an Arch-style mirror-status JSON document (e.g.
+    /// `https://archlinux.org/mirrors/status/json/`) used to drop unhealthy
+    /// mirrors before version/speed checks run. `None` disables prefiltering.
+    pub mirror_status_url: Option<String>,
+    pub mirror_status_timeout: u64,
+    /// Minimum `completion_pct` (0.0-1.0) a mirror must report to pass the prefilter.
+    pub mirror_status_min_completion_pct: f64,
+    /// Maximum `delay` (seconds) a mirror may report to pass the prefilter.
+    pub mirror_status_max_delay_secs: i64,
+
+    /// Minimum number of mirrors that must agree on a version before it is
+    /// accepted as the latest one. Guards against a single mirror reporting a
+    /// corrupt or spoofed `update_number` starving out every honest mirror.
+    pub min_version_agreement: usize,
+    /// Alternative to `min_version_agreement`: the minimum fraction (0.0-1.0)
+    /// of responding mirrors that must agree on a version. `None` disables
+    /// the fraction check and relies on `min_version_agreement` alone.
+    pub min_version_agreement_fraction: Option<f64>,
+
+    /// Wall-clock budget (ms) for sampling a mirror's throughput by streaming
+    /// `url_to_test`. The transfer is aborted once this elapses even if
+    /// `speed_test_max_bytes` hasn't been reached.
+    pub speed_test_timeout: u64,
+    /// Per-mirror byte cap for the throughput sample; the transfer is
+    /// aborted early once this many bytes have been read.
+    pub speed_test_max_bytes: u64,
+    pub speed_test_concurrency: usize,
+
+    /// How long an idle pooled connection is kept alive for reuse across probes.
+    pub http_pool_idle_timeout_ms: u64,
+    /// Number of retries for a transient `version_mirror` failure (connection
+    /// reset / timeout) before the mirror is classified as failed.
+    pub version_mirror_retries: u32,
+    /// Base delay for exponential backoff between `version_mirror` retries.
+    pub version_mirror_retry_backoff_ms: u64,
+}
+
+impl Default for EndeavourOSTarget {
+    fn default() -> Self {
+        Self {
+            mirror_list_file: String::new(),
+            path_to_test: String::new(),
+            comment_prefix: "# ".to_string(),
+            version_mirror_timeout: 3_000,
+            version_mirror_concurrency: 32,
+            fetch_mirrors_timeout: 3_000,
+            mirror_status_url: None,
+            mirror_status_timeout: 5_000,
+            mirror_status_min_completion_pct: 1.0,
+            mirror_status_max_delay_secs: 86_400,
+            min_version_agreement: 3,
+            min_version_agreement_fraction: None,
+            speed_test_timeout: 2_000,
+            speed_test_max_bytes: 2 * 1024 * 1024,
+            speed_test_concurrency: 8,
+            http_pool_idle_timeout_ms: 30_000,
+            version_mirror_retries: 2,
+            version_mirror_retry_backoff_ms: 200,
+        }
+    }
+}
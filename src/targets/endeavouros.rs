@@ -1,17 +1,23 @@
 use crate::config::{AppError, Config, FetchMirrors, LogFormatter};
 use crate::countries::Country;
 use crate::mirror::Mirror;
+use crate::mirror_status::{fetch_mirror_status, is_mirror_healthy};
+use crate::progress::ProgressEvent;
 use crate::target_configs::endeavouros::EndeavourOSTarget;
 use futures::future::join_all;
+use futures::StreamExt;
 use reqwest;
+use std::collections::HashMap;
 use std::fmt::Display;
 use std::fs;
 use std::str::FromStr;
+use std::sync::atomic::{AtomicU64, Ordering};
 use std::sync::{mpsc, Arc};
 use std::time::Duration;
 use tokio;
 use tokio::runtime::Runtime;
 use tokio::sync::Semaphore;
+use tokio::time::{timeout, Instant};
 use url::Url;
 
 struct VersionedMirror {
@@ -19,43 +25,109 @@ struct VersionedMirror {
     pub update_number: Option<usize>,
 }
 
+/// Builds the single pooled client shared across all mirror probes, so the
+/// hundreds of concurrent requests gated by the semaphore reuse TCP/TLS
+/// connections and DNS results instead of each opening their own. HTTP/2 is
+/// negotiated automatically over TLS; `pool_idle_timeout` controls how long
+/// an idle connection is kept around for reuse.
+fn build_http_client(target: &EndeavourOSTarget) -> reqwest::Client {
+    reqwest::Client::builder()
+        .pool_idle_timeout(Duration::from_millis(target.http_pool_idle_timeout_ms))
+        .build()
+        .expect("failed to build http client")
+}
+
+/// Sends a GET with bounded retry and exponential backoff, so a single
+/// dropped packet doesn't permanently classify an otherwise-good mirror as
+/// failed. Only transient errors (connect/timeout) are retried.
+///
+/// `request_timeout` is a deadline for the *whole* call, not each attempt: a
+/// per-attempt timeout equal to the full budget would let the first attempt's
+/// own timeout race an outer caller-side timeout of the same length, leaving
+/// no time left for a retry to ever fire. Each attempt instead gets a share
+/// of whatever budget remains, so a stalled connection fails fast enough for
+/// later attempts (and their backoff) to still happen within the deadline.
+async fn get_with_retry(
+    client: &reqwest::Client,
+    url: Url,
+    request_timeout: Duration,
+    retries: u32,
+    backoff_ms: u64,
+) -> Result<reqwest::Response, reqwest::Error> {
+    let deadline = Instant::now() + request_timeout;
+    let attempt_timeout = request_timeout / (retries + 1);
+
+    let mut attempt = 0;
+    loop {
+        let remaining = deadline.saturating_duration_since(Instant::now());
+        let this_timeout = attempt_timeout.min(remaining).max(Duration::from_millis(1));
+
+        match client.get(url.clone()).timeout(this_timeout).send().await {
+            Ok(response) => return Ok(response),
+            Err(err) if attempt < retries && (err.is_timeout() || err.is_connect()) => {
+                let backoff = Duration::from_millis(backoff_ms * 2u64.pow(attempt));
+                attempt += 1;
+                let remaining = deadline.saturating_duration_since(Instant::now());
+                if remaining.is_zero() {
+                    return Err(err);
+                }
+                tokio::time::sleep(backoff.min(remaining)).await;
+            }
+            Err(err) => return Err(err),
+        }
+    }
+}
+
 async fn version_mirror(
     mirror: Mirror,
     _config: Arc<Config>,
     target: Arc<EndeavourOSTarget>,
+    client: Arc<reqwest::Client>,
     semaphore: Arc<Semaphore>,
-    tx_progress: mpsc::Sender<String>,
+    tx_progress: mpsc::Sender<ProgressEvent>,
 ) -> VersionedMirror {
     let _permit = semaphore.acquire().await;
 
-    let client = reqwest::Client::new();
-    let response_result = client
-        .get(mirror.url.join("state").unwrap())
-        .timeout(Duration::from_millis(target.version_mirror_timeout))
-        .send()
-        .await;
+    let response_result = get_with_retry(
+        &client,
+        mirror.url.join("state").unwrap(),
+        Duration::from_millis(target.version_mirror_timeout),
+        target.version_mirror_retries,
+        target.version_mirror_retry_backoff_ms,
+    )
+    .await;
 
     let mut update_number = None;
-    let msg = match response_result { Ok(response) => {
+    let url = mirror.url.to_string();
+    let event = match response_result { Ok(response) => {
         match response.text_with_charset("utf-8").await { Ok(output) => {
             if let Some(line) = output.lines().next() {
                 if let Ok(number) = line.parse::<usize>() {
                     update_number = Some(number);
-                    format!("FETCHED MIRROR VERSION {}: {}", number, mirror.url)
+                    ProgressEvent::MirrorVersioned { url, version: number }
                 } else {
-                    format!("FAILED TO READ MIRROR UPDATE NUMBER: {}", mirror.url)
+                    ProgressEvent::MirrorFailed {
+                        url,
+                        reason: "FAILED TO READ MIRROR UPDATE NUMBER".to_string(),
+                    }
                 }
             } else {
-                format!("EMPTY MIRROR STATE: {}", mirror.url)
+                ProgressEvent::EmptyState { url }
             }
         } _ => {
-            format!("FAILED TO READ STATE: {}", mirror.url)
+            ProgressEvent::MirrorFailed {
+                url,
+                reason: "FAILED TO READ STATE".to_string(),
+            }
         }}
     } _ => {
-        format!("FAILED TO CONNECT: {}", mirror.url)
+        ProgressEvent::MirrorFailed {
+            url,
+            reason: "FAILED TO CONNECT".to_string(),
+        }
     }};
 
-    tx_progress.send(msg).unwrap();
+    tx_progress.send(event).unwrap();
 
     VersionedMirror {
         mirror,
@@ -66,8 +138,9 @@ async fn version_mirror(
 fn version_mirrors(
     config: Arc<Config>,
     target: Arc<EndeavourOSTarget>,
+    client: Arc<reqwest::Client>,
     mirrors: Vec<Mirror>,
-    tx_progress: mpsc::Sender<String>,
+    tx_progress: mpsc::Sender<ProgressEvent>,
 ) -> Vec<VersionedMirror> {
     let runtime = tokio::runtime::Runtime::new().unwrap();
     let _sth = runtime.enter();
@@ -79,6 +152,7 @@ fn version_mirrors(
             mirror,
             Arc::clone(&config),
             Arc::clone(&target),
+            Arc::clone(&client),
             Arc::clone(&semaphore),
             mpsc::Sender::clone(&tx_progress),
         ))
@@ -91,6 +165,158 @@ fn version_mirrors(
         .collect::<Vec<_>>()
 }
 
+/// Picks the highest version that at least `min_agreement` mirrors (or, if
+/// configured, a `min_agreement_fraction` of responding mirrors) agree on,
+/// rather than trusting a single mirror's possibly-corrupt max. Mirrors
+/// reporting a version above the chosen one are treated as outliers.
+///
+/// `min_agreement` is clamped to `total_responding` so a small but unanimous
+/// mirror set (fewer mirrors than the configured threshold) still reaches
+/// quorum instead of silently producing an empty result. That clamp would
+/// let a single responder trivially reach "quorum" with itself, so a single
+/// responder is never trusted outright: with fewer than two responding
+/// mirrors no version is accepted.
+fn select_quorum_version(
+    versions: &[usize],
+    min_agreement: usize,
+    min_agreement_fraction: Option<f64>,
+) -> Option<usize> {
+    let total_responding = versions.len();
+    if total_responding < 2 {
+        return None;
+    }
+
+    let min_agreement = min_agreement.min(total_responding);
+
+    let mut histogram: HashMap<usize, usize> = HashMap::new();
+    for &version in versions {
+        *histogram.entry(version).or_insert(0) += 1;
+    }
+
+    let mut candidates: Vec<usize> = histogram.keys().copied().collect();
+    candidates.sort_unstable_by(|a, b| b.cmp(a));
+
+    candidates.into_iter().find(|version| {
+        let count = histogram[version];
+        count >= min_agreement
+            || min_agreement_fraction
+                .is_some_and(|fraction| count as f64 / total_responding as f64 >= fraction)
+    })
+}
+
+struct SpeedTestedMirror {
+    pub mirror: Mirror,
+    pub bytes_per_sec: f64,
+}
+
+async fn measure_throughput(
+    versioned: VersionedMirror,
+    target: Arc<EndeavourOSTarget>,
+    client: Arc<reqwest::Client>,
+    semaphore: Arc<Semaphore>,
+    tx_progress: mpsc::Sender<ProgressEvent>,
+) -> SpeedTestedMirror {
+    let _permit = semaphore.acquire().await;
+    let mirror = versioned.mirror;
+
+    let budget = Duration::from_millis(target.speed_test_timeout);
+    let byte_cap = target.speed_test_max_bytes;
+    let started = Instant::now();
+
+    // `total_bytes` lives outside the timed future so a timeout still
+    // reports the bytes streamed so far instead of collapsing to zero.
+    let total_bytes = Arc::new(AtomicU64::new(0));
+    let stream_total_bytes = Arc::clone(&total_bytes);
+    let url_to_test = mirror.url_to_test.clone();
+
+    let timed_out = timeout(budget, async move {
+        let response = get_with_retry(
+            &client,
+            url_to_test,
+            budget,
+            target.version_mirror_retries,
+            target.version_mirror_retry_backoff_ms,
+        )
+        .await?;
+        let mut stream = response.bytes_stream();
+        while let Some(chunk) = stream.next().await {
+            let len = chunk?.len() as u64;
+            if stream_total_bytes.fetch_add(len, Ordering::Relaxed) + len >= byte_cap {
+                break;
+            }
+        }
+        Ok::<(), reqwest::Error>(())
+    })
+    .await
+    .is_err();
+
+    let bytes_read = total_bytes.load(Ordering::Relaxed);
+    let elapsed_secs = if timed_out {
+        budget.as_secs_f64()
+    } else {
+        started.elapsed().as_secs_f64()
+    }
+    .max(0.001);
+    let bytes_per_sec = bytes_read as f64 / elapsed_secs;
+
+    if bytes_read > 0 {
+        tx_progress
+            .send(ProgressEvent::Stage(format!(
+                "MEASURED {:.0} B/s: {}",
+                bytes_per_sec, mirror.url
+            )))
+            .unwrap();
+    } else {
+        tx_progress
+            .send(ProgressEvent::MirrorFailed {
+                url: mirror.url.to_string(),
+                reason: "FAILED TO MEASURE THROUGHPUT".to_string(),
+            })
+            .unwrap();
+    }
+
+    SpeedTestedMirror {
+        mirror,
+        bytes_per_sec,
+    }
+}
+
+fn rank_by_throughput(
+    target: Arc<EndeavourOSTarget>,
+    client: Arc<reqwest::Client>,
+    mirrors: Vec<VersionedMirror>,
+    tx_progress: mpsc::Sender<ProgressEvent>,
+) -> Vec<Mirror> {
+    let runtime = tokio::runtime::Runtime::new().unwrap();
+    let _sth = runtime.enter();
+
+    let semaphore = Arc::new(Semaphore::new(target.speed_test_concurrency));
+
+    let handles = mirrors.into_iter().map(|versioned| {
+        runtime.spawn(measure_throughput(
+            versioned,
+            Arc::clone(&target),
+            Arc::clone(&client),
+            Arc::clone(&semaphore),
+            mpsc::Sender::clone(&tx_progress),
+        ))
+    });
+
+    let mut speed_tested = runtime
+        .block_on(join_all(handles))
+        .into_iter()
+        .filter_map(|r| r.ok())
+        .collect::<Vec<_>>();
+
+    speed_tested.sort_by(|a, b| {
+        b.bytes_per_sec
+            .partial_cmp(&a.bytes_per_sec)
+            .unwrap_or(std::cmp::Ordering::Equal)
+    });
+
+    speed_tested.into_iter().map(|m| m.mirror).collect()
+}
+
 impl LogFormatter for EndeavourOSTarget {
     fn format_comment(&self, message: impl Display) -> String {
         format!("{}{}", self.comment_prefix, message)
@@ -105,12 +331,15 @@ impl FetchMirrors for EndeavourOSTarget {
     fn fetch_mirrors(
         &self,
         config: Arc<Config>,
-        tx_progress: mpsc::Sender<String>,
+        tx_progress: mpsc::Sender<ProgressEvent>,
     ) -> Result<Vec<Mirror>, AppError> {
+        let client = Arc::new(build_http_client(self));
+
         let output = if let Ok(url) = Url::parse(self.mirror_list_file.as_str()) {
+            let client = Arc::clone(&client);
             Runtime::new().unwrap().block_on(async {
                 Ok::<_, AppError>(
-                    reqwest::Client::new()
+                    client
                         .get(url)
                         .timeout(Duration::from_millis(self.fetch_mirrors_timeout))
                         .send()
@@ -129,7 +358,7 @@ impl FetchMirrors for EndeavourOSTarget {
 
         for line in output.lines() {
             if line.starts_with("## ") {
-                current_country = Country::from_str(line.replace("## ", "").as_str());
+                current_country = Country::from_str(line.replace("## ", "").as_str()).ok();
                 continue;
             }
             if line.starts_with('#') {
@@ -155,36 +384,143 @@ impl FetchMirrors for EndeavourOSTarget {
             }
         }
 
+        let mirrors = if let Some(status_url) = self.mirror_status_url.as_ref() {
+            match Runtime::new().unwrap().block_on(fetch_mirror_status(
+                status_url,
+                Duration::from_millis(self.mirror_status_timeout),
+            )) {
+                Ok(status) => {
+                    let (healthy, unhealthy): (Vec<Mirror>, Vec<Mirror>) =
+                        mirrors.into_iter().partition(|mirror| {
+                            is_mirror_healthy(
+                                mirror,
+                                &status,
+                                self.mirror_status_min_completion_pct,
+                                self.mirror_status_max_delay_secs,
+                            )
+                        });
+
+                    for mirror in unhealthy {
+                        tx_progress
+                            .send(ProgressEvent::MirrorFailed {
+                                url: mirror.url.to_string(),
+                                reason: "DROPPED BY MIRROR STATUS PREFILTER".to_string(),
+                            })
+                            .unwrap();
+                    }
+
+                    healthy
+                }
+                Err(_) => {
+                    // An auxiliary health filter failing shouldn't take down
+                    // the whole pipeline; fall back to treating every mirror
+                    // as healthy, same as mirrors absent from the status JSON.
+                    tx_progress
+                        .send(ProgressEvent::Stage(format!(
+                            "MIRROR STATUS PREFILTER SKIPPED (fetch failed): {}",
+                            status_url
+                        )))
+                        .unwrap();
+                    mirrors
+                }
+            }
+        } else {
+            mirrors
+        };
+
         let versioned_mirrors = version_mirrors(
             Arc::clone(&config),
             Arc::new(self.clone()),
+            Arc::clone(&client),
             mirrors,
             mpsc::Sender::clone(&tx_progress),
         );
 
-        let max_version = versioned_mirrors
+        let observed_versions: Vec<usize> = versioned_mirrors
             .iter()
             .filter_map(|m| m.update_number)
-            .max();
+            .collect();
+
+        let quorum_version = select_quorum_version(
+            &observed_versions,
+            self.min_version_agreement,
+            self.min_version_agreement_fraction,
+        );
 
-        let mirrors = if let Some(version) = max_version {
+        let latest_version_mirrors = if let Some(version) = quorum_version {
             tx_progress
-                .send(format!("TAKING MIRRORS WITH LATEST VERSION: {}", version))
+                .send(ProgressEvent::Stage(format!(
+                    "TAKING MIRRORS WITH LATEST VERSION: {}",
+                    version
+                )))
                 .unwrap();
             versioned_mirrors
                 .into_iter()
-                .filter_map(|m| {
-                    if m.update_number == max_version {
-                        Some(m.mirror)
-                    } else {
+                .filter_map(|m| match m.update_number {
+                    Some(update_number) if update_number == version => Some(m),
+                    Some(update_number) if update_number > version => {
+                        tx_progress
+                            .send(ProgressEvent::MirrorFailed {
+                                url: m.mirror.url.to_string(),
+                                reason: format!("REJECTING OUTLIER MIRROR VERSION {}", update_number),
+                            })
+                            .unwrap();
                         None
                     }
+                    _ => None,
                 })
                 .collect()
         } else {
             Vec::new()
         };
 
+        let mirrors = rank_by_throughput(
+            Arc::new(self.clone()),
+            Arc::clone(&client),
+            latest_version_mirrors,
+            mpsc::Sender::clone(&tx_progress),
+        );
+
         Ok(mirrors)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::select_quorum_version;
+
+    #[test]
+    fn no_responders_is_none() {
+        assert_eq!(select_quorum_version(&[], 3, None), None);
+    }
+
+    #[test]
+    fn single_responder_is_never_trusted() {
+        assert_eq!(select_quorum_version(&[5], 1, None), None);
+        assert_eq!(select_quorum_version(&[5], 0, Some(1.0)), None);
+    }
+
+    #[test]
+    fn picks_highest_version_meeting_agreement() {
+        let versions = [3, 3, 4, 4, 4];
+        assert_eq!(select_quorum_version(&versions, 2, None), Some(4));
+    }
+
+    #[test]
+    fn rejects_outlier_below_agreement_threshold() {
+        let versions = [3, 3, 3, 4];
+        assert_eq!(select_quorum_version(&versions, 3, None), Some(3));
+    }
+
+    #[test]
+    fn min_agreement_clamps_to_small_unanimous_set() {
+        let versions = [7, 7];
+        assert_eq!(select_quorum_version(&versions, 5, None), Some(7));
+    }
+
+    #[test]
+    fn fraction_threshold_can_accept_where_count_alone_would_not() {
+        let versions = [2, 2, 1];
+        assert_eq!(select_quorum_version(&versions, 3, Some(0.5)), Some(2));
+    }
+}